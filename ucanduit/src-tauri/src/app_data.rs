@@ -0,0 +1,233 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Resolves the directory the app persists its JSON documents under,
+/// mirroring the platform conventions `write_json_file`/`read_json_file`
+/// have always used.
+pub(crate) fn app_dir() -> PathBuf {
+    match std::env::var("APPDATA") {
+        Ok(appdata) => Path::new(&appdata).join("ucanduit"),
+        Err(_) => match std::env::var("HOME") {
+            Ok(home) => Path::new(&home).join(".ucanduit"),
+            Err(_) => std::env::current_dir().unwrap().join("data"),
+        },
+    }
+}
+
+/// On-disk shape of every document this module writes: a `schema_version`
+/// so a future reader can tell how to interpret `data`, plus the payload
+/// itself.
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope<T> {
+    schema_version: u32,
+    data: T,
+}
+
+const DEFAULT_SCHEMA_VERSION: u32 = 1;
+
+/// Serializes `data` (wrapped in a schema-versioned envelope) and writes it
+/// under the app dir. The write is atomic: the envelope is serialized to a
+/// `.tmp` file in the same directory and `fs::rename`d over the target, so a
+/// crash mid-write can't leave a truncated or corrupt file. Any existing
+/// file at the target is copied to a rolling `.bak` first.
+pub(crate) fn write_json<T: Serialize>(filename: &str, data: &T) -> Result<(), String> {
+    write_json_versioned(filename, DEFAULT_SCHEMA_VERSION, data)
+}
+
+pub(crate) fn write_json_versioned<T: Serialize>(
+    filename: &str,
+    schema_version: u32,
+    data: &T,
+) -> Result<(), String> {
+    let dir = app_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app directory: {}", e))?;
+
+    let envelope = Envelope { schema_version, data };
+    let json_string = serde_json::to_string_pretty(&envelope)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    let target_path = dir.join(filename);
+    backup_existing(&target_path)?;
+
+    let temp_path = dir.join(format!("{}.tmp", filename));
+    fs::write(&temp_path, json_string).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::rename(&temp_path, &target_path)
+        .map_err(|e| format!("Failed to finalize write to {:?}: {}", target_path, e))
+}
+
+fn backup_existing(target_path: &Path) -> Result<(), String> {
+    if !target_path.exists() {
+        return Ok(());
+    }
+
+    let mut backup_name = target_path.as_os_str().to_os_string();
+    backup_name.push(".bak");
+    fs::copy(target_path, PathBuf::from(backup_name))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to back up {:?}: {}", target_path, e))
+}
+
+/// Reads and deserializes a JSON document previously written with
+/// [`write_json`], ignoring its `schema_version`. Use
+/// [`read_json_migrated`] instead when the caller needs to upgrade older
+/// documents before reading them.
+pub(crate) fn read_json<T: DeserializeOwned>(filename: &str) -> Result<T, String> {
+    read_envelope::<T>(filename).map(|envelope| envelope.data)
+}
+
+fn read_envelope<T: DeserializeOwned>(filename: &str) -> Result<Envelope<T>, String> {
+    let file_path = app_dir().join(filename);
+    if !file_path.exists() {
+        return Err(format!("File does not exist: {}", filename));
+    }
+
+    let contents =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+/// A single upgrade step: given the document at `schema_version`, return it
+/// reshaped for `schema_version + 1`.
+pub(crate) type Migration = fn(JsonValue) -> Result<JsonValue, String>;
+
+/// Reads a document, running any registered migrations to bring it from its
+/// stored `schema_version` up to `current_version` before deserializing.
+pub(crate) fn read_json_migrated<T: DeserializeOwned>(
+    filename: &str,
+    current_version: u32,
+    migrations: &[(u32, Migration)],
+) -> Result<T, String> {
+    let mut envelope = read_envelope::<JsonValue>(filename)?;
+
+    while envelope.schema_version < current_version {
+        let (_, migrate) = migrations
+            .iter()
+            .find(|(from_version, _)| *from_version == envelope.schema_version)
+            .ok_or_else(|| {
+                format!(
+                    "No migration registered for {} from schema version {}",
+                    filename, envelope.schema_version
+                )
+            })?;
+
+        envelope.data = migrate(envelope.data)?;
+        envelope.schema_version += 1;
+    }
+
+    serde_json::from_value(envelope.data)
+        .map_err(|e| format!("Failed to deserialize migrated data: {}", e))
+}
+
+/// Migration chains, keyed by filename, that `read_json_file_migrated`
+/// consults. New entries get added here as a document's schema evolves.
+pub(crate) fn migrations_for(_filename: &str) -> Vec<(u32, Migration)> {
+    Vec::new()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    // `read_json_migrated` resolves the app dir through `HOME`/`APPDATA`;
+    // serialize access so tests pointing those vars at a scratch dir don't race.
+    // Shared with the other modules whose tests repoint those vars.
+    pub(crate) static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    struct TestEnv {
+        _guard: std::sync::MutexGuard<'static, ()>,
+        dir: PathBuf,
+    }
+
+    impl TestEnv {
+        fn new() -> Self {
+            let guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let dir = std::env::temp_dir().join(format!(
+                "ucanduit-app-data-test-{}-{}",
+                std::process::id(),
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            std::env::set_var("HOME", &dir);
+            std::env::remove_var("APPDATA");
+            TestEnv { _guard: guard, dir }
+        }
+    }
+
+    impl Drop for TestEnv {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Widget {
+        name: String,
+        count: u32,
+    }
+
+    fn rename_label_to_name(mut data: JsonValue) -> Result<JsonValue, String> {
+        let obj = data.as_object_mut().ok_or("expected an object")?;
+        if let Some(label) = obj.remove("label") {
+            obj.insert("name".to_string(), label);
+        }
+        Ok(data)
+    }
+
+    fn add_default_count(mut data: JsonValue) -> Result<JsonValue, String> {
+        data.as_object_mut()
+            .ok_or("expected an object")?
+            .entry("count")
+            .or_insert(json!(0));
+        Ok(data)
+    }
+
+    #[test]
+    fn walks_multiple_migrations_in_order() {
+        let _env = TestEnv::new();
+        write_json_versioned("widget.json", 1, &json!({ "label": "gadget" })).unwrap();
+
+        let migrations: Vec<(u32, Migration)> =
+            vec![(1, rename_label_to_name), (2, add_default_count)];
+        let widget: Widget = read_json_migrated("widget.json", 3, &migrations).unwrap();
+        assert_eq!(
+            widget,
+            Widget {
+                name: "gadget".to_string(),
+                count: 0
+            }
+        );
+    }
+
+    #[test]
+    fn already_current_version_skips_migrations() {
+        let _env = TestEnv::new();
+        write_json_versioned("widget.json", 2, &json!({ "name": "gadget", "count": 5 })).unwrap();
+
+        let widget: Widget = read_json_migrated("widget.json", 2, &[]).unwrap();
+        assert_eq!(
+            widget,
+            Widget {
+                name: "gadget".to_string(),
+                count: 5
+            }
+        );
+    }
+
+    #[test]
+    fn missing_migration_step_errors() {
+        let _env = TestEnv::new();
+        write_json_versioned("widget.json", 1, &json!({ "label": "gadget" })).unwrap();
+
+        let result: Result<Widget, String> = read_json_migrated("widget.json", 2, &[]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,327 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rodio::{Decoder, OutputStream, Sink, Source};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, RwLock};
+
+/// Playback volume, clamped to the range rodio's `Sink` accepts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Volume(pub f32);
+
+impl Volume {
+    pub fn clamped(value: f32) -> Self {
+        Volume(value.clamp(0.0, 2.0))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackInfo {
+    pub path: String,
+    pub name: String,
+}
+
+impl TrackInfo {
+    fn from_path(path: &Path) -> Self {
+        TrackInfo {
+            path: path.to_string_lossy().to_string(),
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string()),
+        }
+    }
+}
+
+/// Messages the frontend-facing commands send to the playback worker.
+#[derive(Debug, Clone)]
+pub enum AudioControlMessage {
+    Play(PathBuf),
+    Pause,
+    Resume,
+    Stop,
+    SetVolume(Volume),
+}
+
+/// Messages the playback worker emits back to the webview as Tauri events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum AudioStatusMessage {
+    NowPlaying(TrackInfo),
+    Progress { elapsed: u64, total: u64 },
+    Finished,
+    Error(String),
+}
+
+const STATUS_EVENT: &str = "audio://status";
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Cheaply queryable snapshot of the playback worker's state, kept in sync
+/// by the worker so `get_playback_status` never has to talk to the audio
+/// thread directly.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppState {
+    pub playing: bool,
+    pub current_track: Option<TrackInfo>,
+    pub volume: f32,
+}
+
+/// Long-lived handle to the playback worker. Owns the sending half of the
+/// control channel; the worker owns the `rodio` backend on its own thread
+/// so a blocked or slow decode never stalls a Tauri command.
+pub struct AudioController {
+    command_tx: mpsc::Sender<AudioControlMessage>,
+    state: Arc<RwLock<AppState>>,
+}
+
+impl AudioController {
+    pub fn new(app_handle: AppHandle) -> Self {
+        let (command_tx, command_rx) = mpsc::channel(32);
+        let state = Arc::new(RwLock::new(AppState {
+            volume: 1.0,
+            ..Default::default()
+        }));
+
+        spawn_worker(app_handle, command_rx, Arc::clone(&state));
+
+        AudioController { command_tx, state }
+    }
+
+    async fn send(&self, message: AudioControlMessage) -> Result<(), String> {
+        self.command_tx
+            .send(message)
+            .await
+            .map_err(|e| format!("Audio worker is not running: {}", e))
+    }
+
+    pub async fn play(&self, path: PathBuf) -> Result<(), String> {
+        self.send(AudioControlMessage::Play(path)).await
+    }
+
+    pub async fn pause(&self) -> Result<(), String> {
+        self.send(AudioControlMessage::Pause).await
+    }
+
+    pub async fn resume(&self) -> Result<(), String> {
+        self.send(AudioControlMessage::Resume).await
+    }
+
+    pub async fn stop(&self) -> Result<(), String> {
+        self.send(AudioControlMessage::Stop).await
+    }
+
+    pub async fn set_volume(&self, volume: Volume) -> Result<(), String> {
+        self.send(AudioControlMessage::SetVolume(volume)).await
+    }
+
+    pub async fn status(&self) -> AppState {
+        self.state.read().await.clone()
+    }
+}
+
+struct ActiveTrack {
+    sink: Sink,
+    total: Option<Duration>,
+    /// When the track is playing, the instant playback last (re)started;
+    /// `None` while paused, so time spent paused isn't counted as elapsed.
+    playing_since: Option<Instant>,
+    /// Play time accumulated across previous play/pause spans.
+    accumulated: Duration,
+}
+
+impl ActiveTrack {
+    fn elapsed(&self) -> Duration {
+        self.accumulated
+            + self
+                .playing_since
+                .map(|instant| instant.elapsed())
+                .unwrap_or_default()
+    }
+
+    fn pause(&mut self) {
+        if let Some(instant) = self.playing_since.take() {
+            self.accumulated += instant.elapsed();
+        }
+    }
+
+    fn resume(&mut self) {
+        if self.playing_since.is_none() {
+            self.playing_since = Some(Instant::now());
+        }
+    }
+}
+
+/// Spawns the worker that owns the `rodio` output stream and sink on a
+/// dedicated OS thread, since `Sink` playback is blocking and shouldn't
+/// share a thread with async command handlers. Polls for control messages
+/// and playback progress on the same loop rather than pulling in a second
+/// timer task.
+fn spawn_worker(
+    app_handle: AppHandle,
+    mut command_rx: mpsc::Receiver<AudioControlMessage>,
+    state: Arc<RwLock<AppState>>,
+) {
+    std::thread::spawn(move || {
+        let (_stream, stream_handle) = match OutputStream::try_default() {
+            Ok(pair) => pair,
+            Err(e) => {
+                emit_status(
+                    &app_handle,
+                    AudioStatusMessage::Error(format!("Failed to open audio output: {}", e)),
+                );
+                return;
+            }
+        };
+
+        let mut active: Option<ActiveTrack> = None;
+        let mut volume: f32 = 1.0;
+
+        'worker: loop {
+            // Drain every queued command before emitting progress, so a
+            // burst (e.g. a volume slider drag) is applied in full each
+            // tick instead of one message per poll interval.
+            loop {
+                match command_rx.try_recv() {
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => break 'worker,
+                    Ok(AudioControlMessage::Play(path)) => match load_sink(&stream_handle, &path) {
+                        Ok((sink, total)) => {
+                            sink.set_volume(volume);
+                            active = Some(ActiveTrack {
+                                sink,
+                                total,
+                                playing_since: Some(Instant::now()),
+                                accumulated: Duration::ZERO,
+                            });
+
+                            let track = TrackInfo::from_path(&path);
+                            set_state(&state, |s| {
+                                s.playing = true;
+                                s.current_track = Some(track.clone());
+                            });
+                            emit_status(&app_handle, AudioStatusMessage::NowPlaying(track));
+                        }
+                        Err(e) => emit_status(&app_handle, AudioStatusMessage::Error(e)),
+                    },
+                    Ok(AudioControlMessage::Pause) => {
+                        if let Some(t) = &mut active {
+                            t.sink.pause();
+                            t.pause();
+                        }
+                        set_state(&state, |s| s.playing = false);
+                    }
+                    Ok(AudioControlMessage::Resume) => {
+                        if let Some(t) = &mut active {
+                            t.sink.play();
+                            t.resume();
+                        }
+                        set_state(&state, |s| s.playing = true);
+                    }
+                    Ok(AudioControlMessage::Stop) => {
+                        active = None;
+                        set_state(&state, |s| {
+                            s.playing = false;
+                            s.current_track = None;
+                        });
+                        emit_status(&app_handle, AudioStatusMessage::Finished);
+                    }
+                    Ok(AudioControlMessage::SetVolume(new_volume)) => {
+                        volume = new_volume.0;
+                        if let Some(t) = &active {
+                            t.sink.set_volume(volume);
+                        }
+                        set_state(&state, |s| s.volume = volume);
+                    }
+                }
+            }
+
+            if let Some(t) = &active {
+                if t.sink.empty() {
+                    active = None;
+                    set_state(&state, |s| {
+                        s.playing = false;
+                        s.current_track = None;
+                    });
+                    emit_status(&app_handle, AudioStatusMessage::Finished);
+                } else {
+                    emit_status(
+                        &app_handle,
+                        AudioStatusMessage::Progress {
+                            elapsed: t.elapsed().as_secs(),
+                            total: t.total.map(|d| d.as_secs()).unwrap_or(0),
+                        },
+                    );
+                }
+            }
+
+            std::thread::sleep(PROGRESS_POLL_INTERVAL);
+        }
+    });
+}
+
+fn load_sink(
+    stream_handle: &rodio::OutputStreamHandle,
+    path: &Path,
+) -> Result<(Sink, Option<Duration>), String> {
+    let file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let source = Decoder::new(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to decode {:?}: {}", path, e))?;
+    let total = source.total_duration();
+    let sink = Sink::try_new(stream_handle).map_err(|e| format!("Failed to create sink: {}", e))?;
+    sink.append(source);
+    Ok((sink, total))
+}
+
+fn set_state(state: &Arc<RwLock<AppState>>, mutate: impl FnOnce(&mut AppState)) {
+    let mut guard = state.blocking_write();
+    mutate(&mut guard);
+}
+
+fn emit_status(app_handle: &AppHandle, message: AudioStatusMessage) {
+    if let Err(e) = app_handle.emit(STATUS_EVENT, &message) {
+        eprintln!("🦀 Failed to emit audio status event: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn play_track(
+    root_id: String,
+    subpath: String,
+    controller: tauri::State<'_, AudioController>,
+) -> Result<(), String> {
+    let path = crate::library_roots::resolve_within_root(&root_id, &subpath)?;
+    controller.play(path).await
+}
+
+#[tauri::command]
+pub async fn pause(controller: tauri::State<'_, AudioController>) -> Result<(), String> {
+    controller.pause().await
+}
+
+#[tauri::command]
+pub async fn resume(controller: tauri::State<'_, AudioController>) -> Result<(), String> {
+    controller.resume().await
+}
+
+#[tauri::command]
+pub async fn stop(controller: tauri::State<'_, AudioController>) -> Result<(), String> {
+    controller.stop().await
+}
+
+#[tauri::command]
+pub async fn set_volume(
+    volume: f32,
+    controller: tauri::State<'_, AudioController>,
+) -> Result<(), String> {
+    controller.set_volume(Volume::clamped(volume)).await
+}
+
+#[tauri::command]
+pub async fn get_playback_status(
+    controller: tauri::State<'_, AudioController>,
+) -> Result<AppState, String> {
+    Ok(controller.status().await)
+}
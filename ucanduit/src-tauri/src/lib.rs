@@ -1,14 +1,36 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value as JsonValue};
+use tauri::Manager;
 
-#[derive(Debug, Serialize, Deserialize)]
+mod app_data;
+mod audio_controller;
+mod library_index;
+mod library_roots;
+mod metadata;
+mod scan_cache;
+
+use audio_controller::{
+    get_playback_status, pause, play_track, resume, set_volume, stop, AudioController,
+};
+use library_index::{reindex_library, search_library, set_index_config, LibraryIndexHandle};
+use library_roots::{add_library_root, list_library_roots};
+use scan_cache::{ScanCache, ScanStats};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioFile {
     pub name: String,
     pub path: String,
     pub size: u64,
     pub extension: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub genre: Option<String>,
+    pub duration_seconds: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,10 +38,14 @@ pub struct DirectoryContents {
     pub directory: String,
     pub files: Vec<AudioFile>,
     pub count: usize,
+    pub errors: Vec<String>,
+    pub scanned: usize,
+    pub cache_hits: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AudioDirectory {
+    pub root_id: String,
     pub name: String,
     pub path: String,
     pub file_count: usize,
@@ -27,104 +53,181 @@ pub struct AudioDirectory {
 
 const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "ogg", "m4a", "aac", "flac", "wma"];
 
-#[tauri::command]
-async fn scan_audio_directories() -> Result<Vec<AudioDirectory>, String> {
+/// Resolves the app's default music library root, `<project_root>/public/audio`.
+/// Seeded into `library_roots` as the `"default"` root the first time roots
+/// are read, so it's scanned and indexed the same way as any other
+/// registered root rather than being special-cased by this subsystem.
+pub(crate) fn default_audio_root() -> Result<PathBuf, String> {
     let current_dir = std::env::current_dir().map_err(|e| format!("Failed to get current dir: {}", e))?;
     let project_root = current_dir.parent().ok_or("Cannot find project root")?;
-    let audio_path = project_root.join("public").join("audio");
-    
+    Ok(project_root.join("public").join("audio"))
+}
+
+#[tauri::command]
+async fn scan_audio_directories(root_id: Option<String>) -> Result<Vec<AudioDirectory>, String> {
+    let root_id = root_id.unwrap_or_else(|| "default".to_string());
+    let audio_path = library_roots::resolve_within_root(&root_id, "")?;
+
     if !audio_path.exists() {
-        return Err("public/audio directory does not exist".to_string());
+        return Err(format!("Library root does not exist: {}", root_id));
     }
-    
+
     let mut audio_directories = Vec::new();
-    
+
     for entry in fs::read_dir(&audio_path).map_err(|e| format!("Failed to read audio dir: {}", e))? {
         let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
         let entry_path = entry.path();
-        
+
         if entry_path.is_dir() {
             let dir_name = entry_path.file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
                 .to_string();
-            
+
             let audio_file_count = count_audio_files(&entry_path);
-            
+
             if audio_file_count > 0 {
                 audio_directories.push(AudioDirectory {
-                    name: dir_name,
-                    path: format!("/audio/{}", entry_path.file_name().unwrap().to_string_lossy()),
+                    root_id: root_id.clone(),
+                    name: dir_name.clone(),
+                    path: dir_name,
                     file_count: audio_file_count,
                 });
             }
         }
     }
-    
+
     Ok(audio_directories)
 }
 
 #[tauri::command]
-async fn scan_audio_directory(directory_path: String) -> Result<DirectoryContents, String> {
-    println!("🦀 Scanning directory: {}", directory_path);
-    
-    let current_dir = std::env::current_dir().map_err(|e| format!("Failed to get current dir: {}", e))?;
-    let project_root = current_dir.parent().ok_or("Cannot find project root")?;
-    let path = if directory_path.starts_with('/') {
-        project_root.join("public").join(&directory_path[1..])
-    } else {
-        project_root.join("public").join(&directory_path)
-    };
-    
+async fn scan_audio_directory(
+    root_id: String,
+    subpath: String,
+    max_depth: Option<u32>,
+    force: Option<bool>,
+) -> Result<DirectoryContents, String> {
+    println!("🦀 Scanning library root {} subpath {}", root_id, subpath);
+
+    let path = library_roots::resolve_within_root(&root_id, &subpath)?;
+
     println!("🦀 Resolved path: {:?}", path);
-    
-    if !path.exists() {
-        return Err(format!("Directory does not exist: {}", directory_path));
-    }
-    
+
     if !path.is_dir() {
-        return Err(format!("Path is not a directory: {}", directory_path));
+        return Err(format!("Path is not a directory: {}", subpath));
     }
-    
+
     let mut audio_files = Vec::new();
-    
-    for entry in fs::read_dir(&path).map_err(|e| format!("Failed to read directory: {}", e))? {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let file_path = entry.path();
-        
-        if file_path.is_file() {
-            if let Some(extension) = file_path.extension() {
-                if let Some(ext_str) = extension.to_str() {
-                    let ext_lower = ext_str.to_lowercase();
-                    if SUPPORTED_AUDIO_EXTENSIONS.contains(&ext_lower.as_str()) {
-                        if let Some(file_name) = file_path.file_name() {
-                            if let Some(name_str) = file_name.to_str() {
-                                let metadata = fs::metadata(&file_path);
-                                let size = metadata.map(|m| m.len()).unwrap_or(0);
-                                
-                                audio_files.push(AudioFile {
-                                    name: name_str.to_string(),
-                                    path: file_path.to_string_lossy().to_string(), // Use absolute path
-                                    size,
-                                    extension: ext_lower,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    let mut errors = Vec::new();
+    let mut visited_dirs = HashSet::new();
+    let mut stats = ScanStats::default();
+    let force = force.unwrap_or(false);
+
+    let (_, save_result) = scan_cache::with_cache(|cache| {
+        collect_audio_files(
+            &path,
+            0,
+            max_depth,
+            &mut visited_dirs,
+            &mut audio_files,
+            &mut errors,
+            cache,
+            force,
+            &mut stats,
+        );
+    });
+
+    if let Err(e) = save_result {
+        errors.push(format!("Failed to persist scan cache: {}", e));
     }
-    
+
     let count = audio_files.len();
-    
+
     Ok(DirectoryContents {
-        directory: directory_path,
+        directory: subpath,
         files: audio_files,
         count,
+        errors,
+        scanned: stats.scanned,
+        cache_hits: stats.cache_hits,
     })
 }
 
+/// Recursively walks `dir`, collecting `AudioFile` entries enriched with
+/// tag metadata. Bounded by `max_depth` (`None` means unlimited) and guards
+/// against symlink loops by tracking each directory's canonical path. Reuses
+/// `cache` for files whose size/mtime haven't changed, unless `force` is set.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn collect_audio_files(
+    dir: &Path,
+    depth: u32,
+    max_depth: Option<u32>,
+    visited_dirs: &mut HashSet<PathBuf>,
+    audio_files: &mut Vec<AudioFile>,
+    errors: &mut Vec<String>,
+    cache: &mut ScanCache,
+    force: bool,
+    stats: &mut ScanStats,
+) {
+    if let Ok(canonical) = fs::canonicalize(dir) {
+        if !visited_dirs.insert(canonical) {
+            return; // already visited this real directory — symlink loop
+        }
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            errors.push(format!("Failed to read directory {:?}: {}", dir, e));
+            return;
+        }
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors.push(format!("Failed to read entry in {:?}: {}", dir, e));
+                continue;
+            }
+        };
+        let file_path = entry.path();
+
+        if file_path.is_dir() {
+            let next_depth = depth + 1;
+            if max_depth.is_none_or(|max| next_depth <= max) {
+                collect_audio_files(
+                    &file_path, next_depth, max_depth, visited_dirs, audio_files, errors, cache,
+                    force, stats,
+                );
+            }
+            continue;
+        }
+
+        if !file_path.is_file() {
+            continue;
+        }
+
+        let Some(ext_str) = file_path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        let ext_lower = ext_str.to_lowercase();
+        if !SUPPORTED_AUDIO_EXTENSIONS.contains(&ext_lower.as_str()) {
+            continue;
+        }
+        let Some(name_str) = file_path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        match scan_cache::resolve_audio_file(
+            &file_path, name_str, &ext_lower, cache, force, stats, errors,
+        ) {
+            Ok(audio_file) => audio_files.push(audio_file),
+            Err(e) => errors.push(e),
+        }
+    }
+}
+
 fn count_audio_files(dir_path: &Path) -> usize {
     let mut count = 0;
     
@@ -157,64 +260,21 @@ async fn get_supported_audio_formats() -> Vec<String> {
 
 #[tauri::command]
 async fn write_json_file(filename: String, data: JsonValue) -> Result<(), String> {
-    let app_dir = match std::env::var("APPDATA") {
-        Ok(appdata) => Path::new(&appdata).join("ucanduit"),
-        Err(_) => {
-            match std::env::var("HOME") {
-                Ok(home) => Path::new(&home).join(".ucanduit"),
-                Err(_) => {
-                    std::env::current_dir().unwrap().join("data")
-                }
-            }
-        }
-    };
-    
-    if let Err(e) = fs::create_dir_all(&app_dir) {
-        return Err(format!("Failed to create app directory: {}", e));
-    }
-    
-    let file_path = app_dir.join(&filename);
-    
-    match serde_json::to_string_pretty(&data) {
-        Ok(json_string) => {
-            match fs::write(&file_path, json_string) {
-                Ok(_) => Ok(()),
-                Err(e) => Err(format!("Failed to write file: {}", e))
-            }
-        },
-        Err(e) => Err(format!("Failed to serialize JSON: {}", e))
-    }
+    app_data::write_json(&filename, &data)
 }
 
 #[tauri::command]
 async fn read_json_file(filename: String) -> Result<JsonValue, String> {
-    let app_dir = match std::env::var("APPDATA") {
-        Ok(appdata) => Path::new(&appdata).join("ucanduit"),
-        Err(_) => {
-            match std::env::var("HOME") {
-                Ok(home) => Path::new(&home).join(".ucanduit"),
-                Err(_) => {
-                    std::env::current_dir().unwrap().join("data")
-                }
-            }
-        }
-    };
-    
-    let file_path = app_dir.join(&filename);
-    
-    if !file_path.exists() {
-        return Err(format!("File does not exist: {}", filename));
-    }
-    
-    match fs::read_to_string(&file_path) {
-        Ok(contents) => {
-            match serde_json::from_str::<JsonValue>(&contents) {
-                Ok(json_data) => Ok(json_data),
-                Err(e) => Err(format!("Failed to parse JSON: {}", e))
-            }
-        },
-        Err(e) => Err(format!("Failed to read file: {}", e))
-    }
+    app_data::read_json(&filename)
+}
+
+#[tauri::command]
+async fn read_json_file_migrated(
+    filename: String,
+    current_version: u32,
+) -> Result<JsonValue, String> {
+    let migrations = app_data::migrations_for(&filename);
+    app_data::read_json_migrated(&filename, current_version, &migrations)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -226,7 +286,19 @@ pub fn run() {
       scan_audio_directories,
       get_supported_audio_formats,
       write_json_file,
-      read_json_file
+      read_json_file,
+      read_json_file_migrated,
+      play_track,
+      pause,
+      resume,
+      stop,
+      set_volume,
+      get_playback_status,
+      search_library,
+      reindex_library,
+      set_index_config,
+      add_library_root,
+      list_library_roots
     ])
     .setup(|app| {
       if cfg!(debug_assertions) {
@@ -236,6 +308,10 @@ pub fn run() {
             .build(),
         )?;
       }
+
+      app.manage(AudioController::new(app.handle().clone()));
+      app.manage(LibraryIndexHandle::new());
+
       Ok(())
     })
     .run(tauri::generate_context!())
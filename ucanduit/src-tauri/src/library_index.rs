@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, RwLock};
+
+use crate::scan_cache::{self, ScanStats};
+use crate::{app_data, collect_audio_files, library_roots, AudioFile};
+
+const INDEX_FILE: &str = "library_index.json";
+const CONFIG_FILE: &str = "library_index_config.json";
+const DEFAULT_REINDEX_EVERY_N_SECONDS: u64 = 3600;
+const DEFAULT_ALBUM_ART_PATTERN: &str = r"(?i)^(cover|folder|album)\.(jpe?g|jpg|png)$";
+const REINDEX_BATCH_SIZE: usize = 200;
+const WORKER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexConfig {
+    pub reindex_every_n_seconds: u64,
+    pub album_art_pattern: String,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        IndexConfig {
+            reindex_every_n_seconds: DEFAULT_REINDEX_EVERY_N_SECONDS,
+            album_art_pattern: DEFAULT_ALBUM_ART_PATTERN.to_string(),
+        }
+    }
+}
+
+/// An `AudioFile` as stored in the index, plus the library root it was
+/// found under and the sibling cover art file (if any) matched by the
+/// configured `album_art_pattern`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedTrack {
+    pub root_id: String,
+    #[serde(flatten)]
+    pub file: AudioFile,
+    pub album_art: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedIndex {
+    tracks: Vec<IndexedTrack>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SearchFilters {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LibraryIndex {
+    tracks_by_path: HashMap<String, IndexedTrack>,
+}
+
+impl LibraryIndex {
+    fn from_tracks(tracks: Vec<IndexedTrack>) -> Self {
+        let tracks_by_path = tracks
+            .into_iter()
+            .map(|track| (track.file.path.clone(), track))
+            .collect();
+        LibraryIndex { tracks_by_path }
+    }
+
+    fn search(&self, query: &str, filters: &SearchFilters) -> Vec<AudioFile> {
+        let query_lower = query.to_lowercase();
+        self.tracks_by_path
+            .values()
+            .filter(|track| matches_filters(track, filters))
+            .filter(|track| query_lower.is_empty() || matches_query(track, &query_lower))
+            .map(|track| track.file.clone())
+            .collect()
+    }
+}
+
+fn matches_query(track: &IndexedTrack, query_lower: &str) -> bool {
+    if track.file.name.to_lowercase().contains(query_lower) {
+        return true;
+    }
+    [&track.file.title, &track.file.artist, &track.file.album, &track.file.genre]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(query_lower))
+}
+
+fn matches_filters(track: &IndexedTrack, filters: &SearchFilters) -> bool {
+    let field_matches = |filter: &Option<String>, value: &Option<String>| {
+        filter.as_ref().is_none_or(|f| {
+            value
+                .as_ref()
+                .is_some_and(|v| v.to_lowercase().contains(&f.to_lowercase()))
+        })
+    };
+
+    field_matches(&filters.artist, &track.file.artist)
+        && field_matches(&filters.album, &track.file.album)
+        && field_matches(&filters.genre, &track.file.genre)
+}
+
+enum IndexCommand {
+    Reindex,
+    UpdateConfig(IndexConfig),
+    Exit,
+}
+
+/// Long-lived handle to the background indexing worker, managed as Tauri
+/// state. Mirrors `AudioController`: callers talk to the worker by sending
+/// commands over a channel rather than touching the index directly.
+pub struct LibraryIndexHandle {
+    command_tx: mpsc::Sender<IndexCommand>,
+    index: Arc<RwLock<LibraryIndex>>,
+}
+
+impl LibraryIndexHandle {
+    pub fn new() -> Self {
+        let (command_tx, command_rx) = mpsc::channel(8);
+
+        let config = app_data::read_json::<IndexConfig>(CONFIG_FILE).unwrap_or_default();
+        let initial_tracks = app_data::read_json::<PersistedIndex>(INDEX_FILE)
+            .map(|persisted| persisted.tracks)
+            .unwrap_or_default();
+        let index = Arc::new(RwLock::new(LibraryIndex::from_tracks(initial_tracks)));
+
+        spawn_worker(config, Arc::clone(&index), command_rx);
+
+        LibraryIndexHandle { command_tx, index }
+    }
+
+    pub async fn search(&self, query: &str, filters: &SearchFilters) -> Vec<AudioFile> {
+        self.index.read().await.search(query, filters)
+    }
+
+    pub async fn request_reindex(&self) -> Result<(), String> {
+        self.command_tx
+            .send(IndexCommand::Reindex)
+            .await
+            .map_err(|e| format!("Library index worker is not running: {}", e))
+    }
+
+    pub async fn set_config(&self, config: IndexConfig) -> Result<(), String> {
+        app_data::write_json(CONFIG_FILE, &config)?;
+        self.command_tx
+            .send(IndexCommand::UpdateConfig(config))
+            .await
+            .map_err(|e| format!("Library index worker is not running: {}", e))
+    }
+}
+
+impl Drop for LibraryIndexHandle {
+    fn drop(&mut self) {
+        let _ = self.command_tx.try_send(IndexCommand::Exit);
+    }
+}
+
+/// Runs the indexing worker on its own thread: rebuilds the index on
+/// `Reindex`, on the configured `reindex_every_n_seconds` cadence, and once
+/// immediately at startup.
+fn spawn_worker(
+    config: IndexConfig,
+    index: Arc<RwLock<LibraryIndex>>,
+    mut command_rx: mpsc::Receiver<IndexCommand>,
+) {
+    std::thread::spawn(move || {
+        let mut album_art_regex = Regex::new(&config.album_art_pattern).ok();
+        let mut reindex_interval = Duration::from_secs(config.reindex_every_n_seconds.max(1));
+
+        // Reindex once up front so the index is fresh even if the configured
+        // interval is longer than the process has been running.
+        rebuild_index(&index, album_art_regex.as_ref());
+        let mut last_reindex = Instant::now();
+
+        loop {
+            match command_rx.try_recv() {
+                Ok(IndexCommand::Reindex) => {
+                    rebuild_index(&index, album_art_regex.as_ref());
+                    last_reindex = Instant::now();
+                }
+                Ok(IndexCommand::UpdateConfig(new_config)) => {
+                    album_art_regex = Regex::new(&new_config.album_art_pattern).ok();
+                    reindex_interval = Duration::from_secs(new_config.reindex_every_n_seconds.max(1));
+                }
+                Ok(IndexCommand::Exit) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => break,
+                Err(mpsc::error::TryRecvError::Empty) => {}
+            }
+
+            if last_reindex.elapsed() >= reindex_interval {
+                rebuild_index(&index, album_art_regex.as_ref());
+                last_reindex = Instant::now();
+            }
+
+            std::thread::sleep(WORKER_POLL_INTERVAL);
+        }
+    });
+}
+
+/// Scans every registered library root and rebuilds the index in batches,
+/// publishing each batch as it's matched with cover art so a concurrent
+/// `search_library` call sees partial results rather than blocking on the
+/// full tree.
+fn rebuild_index(index: &Arc<RwLock<LibraryIndex>>, album_art_regex: Option<&Regex>) {
+    let roots = match library_roots::all_roots() {
+        Ok(roots) => roots,
+        Err(e) => {
+            eprintln!("🦀 Library index: {}", e);
+            return;
+        }
+    };
+
+    let mut art_cache: HashMap<PathBuf, Option<String>> = HashMap::new();
+    let mut rebuilt = LibraryIndex::default();
+
+    let (_, save_result) = scan_cache::with_cache(|cache| {
+        for root in &roots {
+            let root_path = PathBuf::from(&root.path);
+            if !root_path.exists() {
+                continue;
+            }
+
+            let mut files = Vec::new();
+            let mut errors = Vec::new();
+            let mut visited_dirs = HashSet::new();
+            let mut stats = ScanStats::default();
+            collect_audio_files(
+                &root_path,
+                0,
+                None,
+                &mut visited_dirs,
+                &mut files,
+                &mut errors,
+                cache,
+                false,
+                &mut stats,
+            );
+            for error in errors {
+                eprintln!("🦀 Library index scan error ({}): {}", root.id, error);
+            }
+
+            for batch in files.chunks(REINDEX_BATCH_SIZE) {
+                for file in batch {
+                    let album_art =
+                        album_art_regex.and_then(|re| find_album_art(file, re, &mut art_cache));
+                    rebuilt.tracks_by_path.insert(
+                        file.path.clone(),
+                        IndexedTrack {
+                            root_id: root.id.clone(),
+                            file: file.clone(),
+                            album_art,
+                        },
+                    );
+                }
+
+                let mut guard = index.blocking_write();
+                *guard = rebuilt.clone();
+            }
+        }
+    });
+
+    if let Err(e) = save_result {
+        eprintln!("🦀 Failed to persist scan cache: {}", e);
+    }
+
+    let persisted = PersistedIndex {
+        tracks: rebuilt.tracks_by_path.into_values().collect(),
+    };
+    if let Err(e) = app_data::write_json(INDEX_FILE, &persisted) {
+        eprintln!("🦀 Failed to persist library index: {}", e);
+    }
+}
+
+fn find_album_art(
+    file: &AudioFile,
+    regex: &Regex,
+    cache: &mut HashMap<PathBuf, Option<String>>,
+) -> Option<String> {
+    let dir = PathBuf::from(&file.path).parent()?.to_path_buf();
+
+    if let Some(cached) = cache.get(&dir) {
+        return cached.clone();
+    }
+
+    let found = fs::read_dir(&dir).ok().and_then(|entries| {
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| regex.is_match(name))
+            })
+            .map(|path| path.to_string_lossy().to_string())
+    });
+
+    cache.insert(dir, found.clone());
+    found
+}
+
+#[tauri::command]
+pub async fn search_library(
+    query: String,
+    filters: SearchFilters,
+    handle: tauri::State<'_, LibraryIndexHandle>,
+) -> Result<Vec<AudioFile>, String> {
+    Ok(handle.search(&query, &filters).await)
+}
+
+#[tauri::command]
+pub async fn reindex_library(handle: tauri::State<'_, LibraryIndexHandle>) -> Result<(), String> {
+    handle.request_reindex().await
+}
+
+#[tauri::command]
+pub async fn set_index_config(
+    config: IndexConfig,
+    handle: tauri::State<'_, LibraryIndexHandle>,
+) -> Result<(), String> {
+    handle.set_config(config).await
+}
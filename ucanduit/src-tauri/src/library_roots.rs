@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{app_data, default_audio_root};
+
+const ROOTS_FILE: &str = "library_roots.json";
+const DEFAULT_ROOT_ID: &str = "default";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryRoot {
+    pub id: String,
+    pub path: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RootsConfig {
+    roots: Vec<LibraryRoot>,
+}
+
+fn load_config() -> RootsConfig {
+    app_data::read_json(ROOTS_FILE).unwrap_or_default()
+}
+
+fn save_config(config: &RootsConfig) -> Result<(), String> {
+    app_data::write_json(ROOTS_FILE, config)
+}
+
+/// Registers the legacy `public/audio` folder as the `"default"` root the
+/// first time roots are read, so existing scans of that folder keep
+/// working without the caller having to register anything up front.
+fn ensure_default_root(config: &mut RootsConfig) -> Result<(), String> {
+    if config.roots.iter().any(|root| root.id == DEFAULT_ROOT_ID) {
+        return Ok(());
+    }
+
+    let default_root = default_audio_root()?;
+    if default_root.exists() {
+        config.roots.push(LibraryRoot {
+            id: DEFAULT_ROOT_ID.to_string(),
+            path: default_root.to_string_lossy().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn root_id_for_path(canonical_path: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_path.to_string_lossy().as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..16].to_string()
+}
+
+#[tauri::command]
+pub async fn add_library_root(path: String) -> Result<LibraryRoot, String> {
+    let candidate = PathBuf::from(&path);
+    let canonical = fs::canonicalize(&candidate)
+        .map_err(|e| format!("Cannot resolve library root {:?}: {}", candidate, e))?;
+
+    if !canonical.is_dir() {
+        return Err(format!("Library root is not a directory: {:?}", canonical));
+    }
+
+    let mut config = load_config();
+    ensure_default_root(&mut config)?;
+
+    if let Some(existing) = config
+        .roots
+        .iter()
+        .find(|root| Path::new(&root.path) == canonical)
+    {
+        return Ok(existing.clone());
+    }
+
+    let root = LibraryRoot {
+        id: root_id_for_path(&canonical),
+        path: canonical.to_string_lossy().to_string(),
+    };
+    config.roots.push(root.clone());
+    save_config(&config)?;
+
+    Ok(root)
+}
+
+/// Returns every registered library root, seeding the `"default"` root
+/// first if it isn't present yet. Shared by the `list_library_roots`
+/// command and any subsystem (e.g. the background library index) that
+/// needs to scan every registered root rather than just one.
+pub(crate) fn all_roots() -> Result<Vec<LibraryRoot>, String> {
+    let mut config = load_config();
+    let already_had_default = config.roots.iter().any(|root| root.id == DEFAULT_ROOT_ID);
+    ensure_default_root(&mut config)?;
+
+    if !already_had_default && config.roots.iter().any(|root| root.id == DEFAULT_ROOT_ID) {
+        save_config(&config)?;
+    }
+
+    Ok(config.roots)
+}
+
+#[tauri::command]
+pub async fn list_library_roots() -> Result<Vec<LibraryRoot>, String> {
+    all_roots()
+}
+
+/// Resolves `root_id` + `relative_subpath` to an absolute, existing path,
+/// rejecting anything that would land outside the registered root. Both
+/// the root and the candidate are canonicalized, so `..` segments, and
+/// symlinks that resolve outside the root, are caught by the containment
+/// check rather than by string matching on the input.
+pub(crate) fn resolve_within_root(root_id: &str, relative_subpath: &str) -> Result<PathBuf, String> {
+    let mut config = load_config();
+    ensure_default_root(&mut config)?;
+
+    let root = config
+        .roots
+        .into_iter()
+        .find(|root| root.id == root_id)
+        .ok_or_else(|| format!("Unknown library root: {}", root_id))?;
+
+    let root_canonical = fs::canonicalize(&root.path)
+        .map_err(|e| format!("Library root {:?} is no longer accessible: {}", root.path, e))?;
+
+    let relative = relative_subpath.trim_start_matches(['/', '\\']);
+    let candidate = if relative.is_empty() {
+        root_canonical.clone()
+    } else {
+        root_canonical.join(relative)
+    };
+
+    let canonical_candidate = fs::canonicalize(&candidate)
+        .map_err(|e| format!("Cannot resolve path {:?}: {}", candidate, e))?;
+
+    if !canonical_candidate.starts_with(&root_canonical) {
+        return Err(format!(
+            "Path \"{}\" escapes library root {}",
+            relative_subpath, root_id
+        ));
+    }
+
+    Ok(canonical_candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_data::tests::ENV_LOCK;
+
+    // `resolve_within_root` reads its config through `app_data`, which
+    // resolves the app dir from the `HOME`/`APPDATA` env vars. Serialize
+    // access (with `app_data`'s own tests, through the same lock) so tests
+    // that point those vars at a scratch dir don't race.
+
+    struct TestEnv {
+        _guard: std::sync::MutexGuard<'static, ()>,
+        dir: PathBuf,
+    }
+
+    impl TestEnv {
+        fn new() -> Self {
+            let guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let dir = std::env::temp_dir().join(format!(
+                "ucanduit-test-{}-{}",
+                std::process::id(),
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            std::env::set_var("HOME", &dir);
+            std::env::remove_var("APPDATA");
+            TestEnv { _guard: guard, dir }
+        }
+
+        fn library_dir(&self) -> PathBuf {
+            let path = self.dir.join("library");
+            fs::create_dir_all(&path).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TestEnv {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn register_root(library_dir: &Path) -> LibraryRoot {
+        let canonical = fs::canonicalize(library_dir).unwrap();
+        let root = LibraryRoot {
+            id: "test-root".to_string(),
+            path: canonical.to_string_lossy().to_string(),
+        };
+        save_config(&RootsConfig {
+            roots: vec![root.clone()],
+        })
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn resolves_legitimate_subpath() {
+        let env = TestEnv::new();
+        let library_dir = env.library_dir();
+        fs::create_dir_all(library_dir.join("album")).unwrap();
+        fs::write(library_dir.join("album").join("track.mp3"), b"").unwrap();
+        register_root(&library_dir);
+
+        let resolved = resolve_within_root("test-root", "album/track.mp3").unwrap();
+        assert_eq!(
+            resolved,
+            fs::canonicalize(library_dir.join("album").join("track.mp3")).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_parent_traversal() {
+        let env = TestEnv::new();
+        let library_dir = env.library_dir();
+        fs::write(env.dir.join("secret.txt"), b"").unwrap();
+        register_root(&library_dir);
+
+        assert!(resolve_within_root("test-root", "../secret.txt").is_err());
+    }
+
+    #[test]
+    fn rejects_absolute_override() {
+        let env = TestEnv::new();
+        let library_dir = env.library_dir();
+        let outside = env.dir.join("outside.txt");
+        fs::write(&outside, b"").unwrap();
+        register_root(&library_dir);
+
+        // `Path::join` replaces the base entirely when the joined path is
+        // itself absolute. Leading separators are stripped first, so this
+        // path is looked up under the root instead, where it doesn't exist;
+        // an absolute path that still replaces the base is caught by the
+        // containment check. Either way it must not silently escape.
+        let result = resolve_within_root("test-root", outside.to_string_lossy().as_ref());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn rejects_symlink_escape() {
+        let env = TestEnv::new();
+        let library_dir = env.library_dir();
+        let outside = env.dir.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), b"").unwrap();
+        std::os::unix::fs::symlink(&outside, library_dir.join("escape")).unwrap();
+        register_root(&library_dir);
+
+        assert!(resolve_within_root("test-root", "escape/secret.txt").is_err());
+    }
+
+    #[test]
+    fn unknown_root_id_errors() {
+        let _env = TestEnv::new();
+        assert!(resolve_within_root("does-not-exist", "foo").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use lofty::file::{AudioFile as _, TaggedFileExt};
+use lofty::tag::Accessor;
+
+/// Tag metadata read from an audio file. Only `title` falls back to a
+/// filename-derived value when the file has no tag for it; the other tag
+/// fields stay `None` when untagged.
+#[derive(Debug, Clone, Default)]
+pub struct TrackTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub genre: Option<String>,
+    pub duration_seconds: Option<f64>,
+}
+
+/// Reads ID3/Vorbis/MP4 tags (via `lofty`) for a single audio file. Falls
+/// back to a filename-derived title when no tag is present, since a file
+/// with no metadata at all should still surface something reasonable.
+pub fn read_tags(path: &Path) -> Result<TrackTags, String> {
+    let tagged_file = lofty::read_from_path(path)
+        .map_err(|e| format!("Failed to read tags from {:?}: {}", path, e))?;
+
+    let duration_seconds = Some(tagged_file.properties().duration().as_secs_f64());
+
+    let tag = tagged_file.primary_tag().or_else(|| tagged_file.first_tag());
+
+    let mut tags = TrackTags {
+        duration_seconds,
+        ..Default::default()
+    };
+
+    if let Some(tag) = tag {
+        tags.title = tag.title().map(|s| s.to_string());
+        tags.artist = tag.artist().map(|s| s.to_string());
+        tags.album = tag.album().map(|s| s.to_string());
+        tags.track_number = tag.track();
+        tags.genre = tag.genre().map(|s| s.to_string());
+    }
+
+    if tags.title.is_none() {
+        tags.title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string());
+    }
+
+    Ok(tags)
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{app_data, metadata, AudioFile};
+
+const CACHE_FILE: &str = "scan_cache.json";
+
+// Foreground scan commands and the background library-index worker
+// (`library_index::rebuild_index`) each do their own load-mutate-save
+// cycle against `scan_cache.json` from different threads. Without this
+// lock, whichever one saves last silently clobbers the other's updates.
+static CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs `mutate` against a freshly loaded `ScanCache` and persists the
+/// result, holding `CACHE_LOCK` for the whole load-mutate-save cycle so
+/// concurrent callers can't interleave and lose each other's updates.
+pub(crate) fn with_cache<T>(mutate: impl FnOnce(&mut ScanCache) -> T) -> (T, Result<(), String>) {
+    let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut cache = ScanCache::load();
+    let result = mutate(&mut cache);
+    let save_result = cache.save();
+    (result, save_result)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    modified_unix: u64,
+    file: AudioFile,
+}
+
+/// Persisted cache of previously-scanned files, keyed by absolute path, so
+/// a rescan can skip re-reading tags when neither size nor mtime has
+/// changed since the last scan.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ScanCache {
+    pub fn load() -> Self {
+        app_data::read_json(CACHE_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        app_data::write_json(CACHE_FILE, self)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct ScanStats {
+    pub scanned: usize,
+    pub cache_hits: usize,
+}
+
+/// Builds an `AudioFile` for `path`, reusing the cached size/mtime/tags
+/// when neither size nor mtime has changed since the last scan. `force`
+/// bypasses the cache lookup (the entry is still refreshed afterwards).
+pub(crate) fn resolve_audio_file(
+    path: &Path,
+    name: &str,
+    extension: &str,
+    cache: &mut ScanCache,
+    force: bool,
+    stats: &mut ScanStats,
+    errors: &mut Vec<String>,
+) -> Result<AudioFile, String> {
+    let meta = fs::metadata(path).map_err(|e| format!("Failed to stat {:?}: {}", path, e))?;
+    let size = meta.len();
+    let modified_unix = meta
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let key = path.to_string_lossy().to_string();
+
+    if !force {
+        if let Some(entry) = cache.entries.get(&key) {
+            if entry.size == size && entry.modified_unix == modified_unix {
+                stats.scanned += 1;
+                stats.cache_hits += 1;
+                return Ok(entry.file.clone());
+            }
+        }
+    }
+
+    let tags = metadata::read_tags(path).unwrap_or_else(|e| {
+        errors.push(e);
+        metadata::TrackTags::default()
+    });
+
+    let file = AudioFile {
+        name: name.to_string(),
+        path: key.clone(),
+        size,
+        extension: extension.to_string(),
+        title: tags.title,
+        artist: tags.artist,
+        album: tags.album,
+        track_number: tags.track_number,
+        genre: tags.genre,
+        duration_seconds: tags.duration_seconds,
+    };
+
+    cache.entries.insert(
+        key,
+        CacheEntry {
+            size,
+            modified_unix,
+            file: file.clone(),
+        },
+    );
+    stats.scanned += 1;
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn write_temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "ucanduit-scan-cache-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn second_scan_of_unchanged_file_is_a_cache_hit() {
+        let path = write_temp_file("track.mp3", b"not real audio");
+        let mut cache = ScanCache::default();
+        let mut errors = Vec::new();
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, false, &mut stats, &mut errors)
+            .unwrap();
+        assert_eq!(stats.scanned, 1);
+        assert_eq!(stats.cache_hits, 0);
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, false, &mut stats, &mut errors)
+            .unwrap();
+        assert_eq!(stats.scanned, 1);
+        assert_eq!(stats.cache_hits, 1);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn changed_size_invalidates_the_cache_entry() {
+        let path = write_temp_file("track.mp3", b"short");
+        let mut cache = ScanCache::default();
+        let mut errors = Vec::new();
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, false, &mut stats, &mut errors)
+            .unwrap();
+
+        fs::write(&path, b"a much longer body than the original").unwrap();
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, false, &mut stats, &mut errors)
+            .unwrap();
+        assert_eq!(stats.cache_hits, 0, "changed file size should miss the cache");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn force_bypasses_a_valid_cache_entry() {
+        let path = write_temp_file("track.mp3", b"not real audio");
+        let mut cache = ScanCache::default();
+        let mut errors = Vec::new();
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, false, &mut stats, &mut errors)
+            .unwrap();
+
+        let mut stats = ScanStats::default();
+        resolve_audio_file(&path, "track.mp3", "mp3", &mut cache, true, &mut stats, &mut errors)
+            .unwrap();
+        assert_eq!(
+            stats.cache_hits, 0,
+            "force should skip the cache even though the entry is still valid"
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}